@@ -0,0 +1,209 @@
+//! Audio input-device enumeration and selection
+//!
+//! On macOS, AVFoundation device indices are opaque, so there's no way to tell
+//! which microphone is actually in use without asking ffmpeg to list them. This
+//! module shells out to ffmpeg (and Linux's pulse/ALSA tooling) to enumerate
+//! input devices and records the user's choice in managed state so the
+//! streaming capture path can bind to it instead of relying on the system
+//! default.
+
+use std::process::Command;
+use std::sync::Mutex;
+
+/// One audio input device as reported by the platform's enumeration tool.
+///
+/// Deliberately has no index/id field: the only key `set_input_device`
+/// accepts is `name`, since the tool-reported index (ffmpeg's avfoundation
+/// index, `pactl`'s source index, ALSA's card number) has no relationship to
+/// cpal's own enumeration order and binding to it would pick an arbitrary
+/// device.
+#[derive(Clone, serde::Serialize)]
+pub struct AudioInputDevice {
+    name: String,
+    kind: String,
+}
+
+/// Holds the *name* of the input device the user selected, if any. `None`
+/// means "use the system default". We key on name rather than the index
+/// reported here because that index comes from ffmpeg/pactl/arecord and has
+/// no relationship to cpal's own device enumeration order, which is what the
+/// streaming capture path actually binds to.
+#[derive(Default)]
+pub struct InputDeviceState(Mutex<Option<String>>);
+
+/// Read the currently selected input device name, if any. Used by the
+/// streaming capture path to bind to a non-default microphone.
+pub(crate) fn get_selected_input_device(state: &InputDeviceState) -> Option<String> {
+    state.0.lock().ok().and_then(|guard| guard.clone())
+}
+
+/// List available audio input devices for the current platform.
+#[tauri::command]
+pub fn list_audio_input_devices() -> Result<Vec<AudioInputDevice>, String> {
+    #[cfg(target_os = "macos")]
+    {
+        list_avfoundation_devices()
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        list_pulse_or_alsa_devices()
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        Ok(Vec::new())
+    }
+}
+
+/// Record the chosen input device (by name, as returned from
+/// `list_audio_input_devices`) so the streaming/capture path binds to it
+/// instead of the system default.
+#[tauri::command]
+pub fn set_input_device(state: tauri::State<'_, InputDeviceState>, name: String) -> Result<(), String> {
+    let mut guard = state.0.lock().map_err(|_| "Input device state poisoned".to_string())?;
+    log::info!("Selected audio input device: {}", name);
+    *guard = Some(name);
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn list_avfoundation_devices() -> Result<Vec<AudioInputDevice>, String> {
+    // ffmpeg always exits non-zero for a `-list_devices` probe; the listing
+    // itself is printed to stderr, not stdout.
+    let output = Command::new("ffmpeg")
+        .args(["-f", "avfoundation", "-list_devices", "true", "-i", ""])
+        .output()
+        .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+
+    Ok(parse_avfoundation_devices(&String::from_utf8_lossy(&output.stderr)))
+}
+
+#[cfg(target_os = "macos")]
+fn parse_avfoundation_devices(stderr: &str) -> Vec<AudioInputDevice> {
+    // ffmpeg prints two sections, e.g.:
+    //   ... AVFoundation video devices:
+    //   ... [0] FaceTime HD Camera
+    //   ... AVFoundation audio devices:
+    //   ... [0] MacBook Pro Microphone
+    let mut devices = Vec::new();
+    let mut in_audio_section = false;
+
+    for line in stderr.lines() {
+        if line.contains("AVFoundation audio devices") {
+            in_audio_section = true;
+            continue;
+        }
+        if line.contains("AVFoundation video devices") {
+            in_audio_section = false;
+            continue;
+        }
+        if !in_audio_section {
+            continue;
+        }
+
+        if let Some(name) = parse_indexed_device_line(line) {
+            devices.push(AudioInputDevice {
+                name,
+                kind: "avfoundation".to_string(),
+            });
+        }
+    }
+
+    devices
+}
+
+#[cfg(target_os = "macos")]
+fn parse_indexed_device_line(line: &str) -> Option<String> {
+    // Lines look like "[AVFoundation indev @ 0x7f...] [0] MacBook Pro Microphone":
+    // the device index is the *last* bracketed token, not the logger prefix.
+    // We only need it to locate where the name starts, not to surface it
+    // (see `AudioInputDevice`'s doc comment).
+    let close = line.rfind(']')?;
+    let open = line[..close].rfind('[')?;
+    line[open + 1..close].parse::<i32>().ok()?;
+    Some(line[close + 1..].trim().to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn list_pulse_or_alsa_devices() -> Result<Vec<AudioInputDevice>, String> {
+    match Command::new("pactl").args(["list", "short", "sources"]).output() {
+        Ok(output) if output.status.success() => {
+            Ok(parse_pactl_sources(&String::from_utf8_lossy(&output.stdout)))
+        }
+        _ => list_arecord_devices(),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn parse_pactl_sources(stdout: &str) -> Vec<AudioInputDevice> {
+    // e.g. "0\talsa_input.pci-0000_00_1f.3.analog-stereo\tmodule-alsa-card.c\ts16le 2ch 44100Hz\tRUNNING"
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            fields.next()?.trim().parse::<i32>().ok()?;
+            let name = fields.next()?.trim().to_string();
+            Some(AudioInputDevice {
+                name,
+                kind: "pulse".to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn list_arecord_devices() -> Result<Vec<AudioInputDevice>, String> {
+    let output = Command::new("arecord")
+        .arg("-l")
+        .output()
+        .map_err(|e| format!("Failed to run arecord: {}", e))?;
+
+    Ok(parse_arecord_devices(&String::from_utf8_lossy(&output.stdout)))
+}
+
+#[cfg(target_os = "linux")]
+fn parse_arecord_devices(stdout: &str) -> Vec<AudioInputDevice> {
+    // e.g. "card 0: PCH [HDA Intel PCH], device 0: ALC3234 Analog [ALC3234 Analog]"
+    stdout
+        .lines()
+        .filter(|line| line.starts_with("card "))
+        .filter_map(|line| {
+            line.trim_start_matches("card ")
+                .split(':')
+                .next()?
+                .trim()
+                .parse::<i32>()
+                .ok()?;
+            let name = line.split(':').nth(1)?.trim().to_string();
+            Some(AudioInputDevice {
+                name,
+                kind: "alsa".to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(all(test, target_os = "macos"))]
+mod tests {
+    use super::*;
+
+    // Captured from `ffmpeg -f avfoundation -list_devices true -i ""` on macOS 14.
+    const FFMPEG_LIST_DEVICES_STDERR: &str = "\
+[AVFoundation indev @ 0x7f8b3b904f00] AVFoundation video devices:
+[AVFoundation indev @ 0x7f8b3b904f00] [0] FaceTime HD Camera
+[AVFoundation indev @ 0x7f8b3b904f00] [1] Capture screen 0
+[AVFoundation indev @ 0x7f8b3b904f00] AVFoundation audio devices:
+[AVFoundation indev @ 0x7f8b3b904f00] [0] MacBook Pro Microphone
+[AVFoundation indev @ 0x7f8b3b904f00] [1] External Microphone
+";
+
+    #[test]
+    fn parses_real_ffmpeg_avfoundation_listing() {
+        let devices = parse_avfoundation_devices(FFMPEG_LIST_DEVICES_STDERR);
+
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0].name, "MacBook Pro Microphone");
+        assert_eq!(devices[1].name, "External Microphone");
+    }
+}
@@ -0,0 +1,296 @@
+//! Whisper ggml model management
+//!
+//! `get_whisper_model_path` used to hardcode `ggml-base.bin`, and `ensure_whisper_model`
+//! just curled/wgot it with no progress or verification. This module replaces both: it
+//! knows the full ggml lineup (tiny/base/small/medium/large, plus `.en` and quantized
+//! variants), downloads the selected model in-process via reqwest while streaming
+//! `model://download-progress` events, verifies the result against the known SHA-1
+//! checksum from the whisper.cpp manifest (the manifest publishes SHA-1, not SHA-256),
+//! and only renames it into place on success. The active model is tracked here so the
+//! transcription backends can resolve it instead of a fixed path.
+
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use futures_util::StreamExt;
+use sha1::{Digest, Sha1};
+use tauri::{AppHandle, Emitter};
+
+/// Static catalog entry for one ggml model variant, as published in the
+/// whisper.cpp model manifest.
+struct ModelSpec {
+    name: &'static str,
+    url: &'static str,
+    sha1: &'static str,
+    size_bytes: u64,
+}
+
+const MODELS: &[ModelSpec] = &[
+    ModelSpec {
+        name: "tiny",
+        url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-tiny.bin",
+        sha1: "bd577a113a864445d4c299885e0cb97d4ba92b5e",
+        size_bytes: 77_704_715,
+    },
+    ModelSpec {
+        name: "tiny.en",
+        url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-tiny.en.bin",
+        sha1: "c78c86eb1a8faa21b369bcd33207cc90d64ae9df",
+        size_bytes: 77_704_715,
+    },
+    ModelSpec {
+        name: "base",
+        url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base.bin",
+        sha1: "465707469ff3a37a2b9b8d8f89f2f99de7299dac",
+        size_bytes: 147_964_211,
+    },
+    ModelSpec {
+        name: "base.en",
+        url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base.en.bin",
+        sha1: "137c40403d78fd54d454da0f9bd998f78703390c",
+        size_bytes: 147_964_211,
+    },
+    ModelSpec {
+        name: "small",
+        url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-small.bin",
+        sha1: "55356645c2b361a969dfd0ef2c5a50d530afd8d5",
+        size_bytes: 487_601_967,
+    },
+    ModelSpec {
+        name: "small.en",
+        url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-small.en.bin",
+        sha1: "db8a495a91d927739e50b3fc1cc4c6b8f6c2d022",
+        size_bytes: 487_601_967,
+    },
+    ModelSpec {
+        name: "medium",
+        url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-medium.bin",
+        sha1: "fd9727b6e1217c2f614f9b698455c4ffd82463b4",
+        size_bytes: 1_528_637_535,
+    },
+    ModelSpec {
+        name: "medium.en",
+        url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-medium.en.bin",
+        sha1: "8c30f0e44ce9560643ebd10bbe50cd20eafd3723",
+        size_bytes: 1_528_637_535,
+    },
+    ModelSpec {
+        name: "large",
+        url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-large-v3.bin",
+        sha1: "ad82bf6a9043ceed055076d0fd39f5f186ff8062",
+        size_bytes: 3_095_033_483,
+    },
+    ModelSpec {
+        name: "base.en-q5_1",
+        url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base.en-q5_1.bin",
+        sha1: "a3733eb1bc0c0ebd5881104ad5f2452401447430",
+        size_bytes: 59_705_194,
+    },
+    ModelSpec {
+        name: "small.en-q5_1",
+        url: "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-small.en-q5_1.bin",
+        sha1: "6fe57ddcfa49123a025873fbd2a8a9b25c66ac05",
+        size_bytes: 190_821_274,
+    },
+];
+
+/// A model's catalog info merged with local installation/selection state.
+#[derive(Clone, serde::Serialize)]
+pub struct ModelInfo {
+    name: String,
+    size_bytes: u64,
+    installed: bool,
+    active: bool,
+}
+
+/// Progress payload emitted on `model://download-progress`.
+#[derive(Clone, serde::Serialize)]
+struct DownloadProgressPayload {
+    model: String,
+    bytes_downloaded: u64,
+    bytes_total: u64,
+}
+
+/// Tracks which model is active. Empty means "fall back to `base`", matching
+/// the previous hardcoded default.
+#[derive(Default)]
+pub struct ModelManagerState(Mutex<Option<String>>);
+
+fn models_dir() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    PathBuf::from(home).join(".cache/whisper/models")
+}
+
+pub(crate) fn model_path(name: &str) -> PathBuf {
+    models_dir().join(format!("ggml-{}.bin", name))
+}
+
+fn find_spec(name: &str) -> Result<&'static ModelSpec, String> {
+    MODELS
+        .iter()
+        .find(|spec| spec.name == name)
+        .ok_or_else(|| format!("Unknown model: {}", name))
+}
+
+/// Resolve the path to the currently active model, falling back to `base` if
+/// none has been explicitly selected.
+pub(crate) fn active_model_path(state: &ModelManagerState) -> PathBuf {
+    let active = state.0.lock().ok().and_then(|guard| guard.clone());
+    model_path(active.as_deref().unwrap_or("base"))
+}
+
+/// List every model in the catalog, annotated with whether it's installed
+/// and whether it's the currently active one.
+#[tauri::command]
+pub fn list_models(state: tauri::State<'_, ModelManagerState>) -> Result<Vec<ModelInfo>, String> {
+    let active = state
+        .0
+        .lock()
+        .map_err(|_| "Model manager state poisoned".to_string())?
+        .clone();
+
+    Ok(MODELS
+        .iter()
+        .map(|spec| ModelInfo {
+            name: spec.name.to_string(),
+            size_bytes: spec.size_bytes,
+            installed: model_path(spec.name).exists(),
+            active: active.as_deref().unwrap_or("base") == spec.name,
+        })
+        .collect())
+}
+
+/// Download `name` if not already installed, streaming `model://download-progress`
+/// events and verifying the result's SHA-1 before renaming it into place.
+#[tauri::command]
+pub async fn download_model(app: AppHandle, name: String) -> Result<(), String> {
+    let spec = find_spec(&name)?;
+    let dest = model_path(spec.name);
+
+    if dest.exists() {
+        return Ok(());
+    }
+
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create model cache dir: {}", e))?;
+    }
+
+    let tmp_path = dest.with_extension("part");
+
+    let response = reqwest::get(spec.url)
+        .await
+        .map_err(|e| format!("Failed to start model download: {}", e))?;
+    let total = response.content_length().unwrap_or(spec.size_bytes);
+
+    let mut file = tokio::fs::File::create(&tmp_path)
+        .await
+        .map_err(|e| format!("Failed to create temp file: {}", e))?;
+
+    let mut hasher = Sha1::new();
+    let mut downloaded: u64 = 0;
+    let mut stream = response.bytes_stream();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| format!("Model download failed: {}", e))?;
+        hasher.update(&chunk);
+
+        tokio::io::AsyncWriteExt::write_all(&mut file, &chunk)
+            .await
+            .map_err(|e| format!("Failed to write model chunk: {}", e))?;
+
+        downloaded += chunk.len() as u64;
+        let _ = app.emit(
+            "model://download-progress",
+            DownloadProgressPayload {
+                model: spec.name.to_string(),
+                bytes_downloaded: downloaded,
+                bytes_total: total,
+            },
+        );
+    }
+
+    drop(file);
+
+    let digest = format!("{:x}", hasher.finalize());
+    if digest != spec.sha1 {
+        let _ = tokio::fs::remove_file(&tmp_path).await;
+        return Err(format!(
+            "Checksum mismatch for model {}: expected {}, got {}",
+            spec.name, spec.sha1, digest
+        ));
+    }
+
+    tokio::fs::rename(&tmp_path, &dest)
+        .await
+        .map_err(|e| format!("Failed to finalize model file: {}", e))?;
+
+    Ok(())
+}
+
+/// Delete an installed model from disk.
+#[tauri::command]
+pub fn delete_model(name: String) -> Result<(), String> {
+    let spec = find_spec(&name)?;
+    let path = model_path(spec.name);
+
+    if path.exists() {
+        std::fs::remove_file(&path).map_err(|e| format!("Failed to delete model {}: {}", name, e))?;
+    }
+
+    Ok(())
+}
+
+/// Select the model the transcription backends should use. Fails if the
+/// model isn't installed yet.
+#[tauri::command]
+pub fn set_active_model(state: tauri::State<'_, ModelManagerState>, name: String) -> Result<(), String> {
+    find_spec(&name)?;
+
+    if !model_path(&name).exists() {
+        return Err(format!("Model {} is not downloaded", name));
+    }
+
+    let mut guard = state
+        .0
+        .lock()
+        .map_err(|_| "Model manager state poisoned".to_string())?;
+    *guard = Some(name);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A SHA-1 hex digest is always 40 characters; a truncated literal (as
+    /// happened before) would otherwise fail silently by never matching any
+    /// real download.
+    #[test]
+    fn catalog_hashes_are_well_formed_sha1() {
+        for spec in MODELS {
+            assert_eq!(
+                spec.sha1.len(),
+                40,
+                "{} has a malformed SHA-1 constant: {}",
+                spec.name,
+                spec.sha1
+            );
+            assert!(
+                spec.sha1.chars().all(|c| c.is_ascii_hexdigit()),
+                "{} SHA-1 constant contains non-hex characters: {}",
+                spec.name,
+                spec.sha1
+            );
+        }
+    }
+
+    /// Pins `base`'s catalog digest against whisper.cpp's published manifest
+    /// value, so a regressed/re-typo'd constant fails a real comparison
+    /// instead of only a format check.
+    #[test]
+    fn base_model_hash_matches_published_manifest() {
+        let spec = find_spec("base").expect("base is always in the catalog");
+        assert_eq!(spec.sha1, "465707469ff3a37a2b9b8d8f89f2f99de7299dac");
+    }
+}
@@ -0,0 +1,375 @@
+//! Real-time streaming transcription with VAD-gated chunking
+//!
+//! Unlike `transcribe_audio`, which transcribes a single pre-recorded WAV, this
+//! module captures microphone audio continuously and feeds it through three
+//! dedicated threads connected by crossbeam channels. The capture thread reads
+//! from cpal and pushes fixed-size frames into a bounded channel. The VAD
+//! worker drains that channel, runs a lightweight energy-based voice activity
+//! detector that marks utterance boundaries once a run of silence exceeds
+//! `SILENCE_DURATION_MS`, and hands utterances off as `TranscribeJob`s over an
+//! unbounded channel rather than transcribing them itself — a full Whisper
+//! decode is too slow to run inline without starving the frame channel. The
+//! decode thread consumes those jobs: partials (throttled to `PARTIAL_INTERVAL`
+//! so a growing utterance isn't re-decoded from scratch on every frame) emit
+//! `stt://partial`, and completed utterances emit `stt://final`. The decode
+//! thread reuses the same `WhisperState` context as `transcribe_audio` to avoid
+//! reloading the model.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use crossbeam_channel::{bounded, unbounded, Receiver, Sender};
+use tauri::{AppHandle, Emitter};
+
+use crate::audio_devices::{get_selected_input_device, InputDeviceState};
+use crate::whisper_stt::{downmix_to_mono, resample_linear, transcribe_embedded, WhisperState};
+
+/// Frame size pushed from the capture thread, in samples per channel.
+const FRAME_SIZE: usize = 1600; // ~100ms at 16kHz
+const TARGET_SAMPLE_RATE: u32 = 16_000;
+/// Short-term RMS below this is treated as silence.
+const SILENCE_RMS_THRESHOLD: f32 = 0.01;
+/// How long a run of silence must last before we close the current utterance.
+const SILENCE_DURATION_MS: u64 = 700;
+/// Minimum time between partial-transcript decodes. A full decode of the
+/// growing utterance is too expensive to run on every ~100ms frame (it would
+/// block the VAD loop and starve `frame_rx`), so partials are rate-limited
+/// and run on a separate thread from frame capture/accumulation.
+const PARTIAL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Payload for the `stt://partial` event: the running hypothesis for the
+/// utterance currently being spoken.
+#[derive(Clone, serde::Serialize)]
+struct PartialTranscriptPayload {
+    text: String,
+}
+
+/// Payload for the `stt://final` event: the transcript of an utterance that
+/// just closed on a detected pause.
+#[derive(Clone, serde::Serialize)]
+struct FinalTranscriptPayload {
+    text: String,
+}
+
+/// A decode job handed from the VAD worker to the dedicated decode thread.
+/// Partial jobs carry the utterance accumulated so far; final jobs close it
+/// out. Kept off the VAD loop so a slow Whisper decode never blocks frame
+/// draining.
+enum TranscribeJob {
+    Partial(Vec<f32>),
+    Final(Vec<f32>),
+}
+
+/// Handle to the background capture, VAD, and decode threads, so
+/// `stop_streaming_transcription` can tear them down cleanly.
+struct StreamHandle {
+    stop_flag: Arc<AtomicBool>,
+    capture_thread: Option<JoinHandle<()>>,
+    worker_thread: Option<JoinHandle<()>>,
+    decode_thread: Option<JoinHandle<()>>,
+}
+
+/// Holds the currently running streaming session, if any.
+#[derive(Default)]
+pub struct StreamingState(Mutex<Option<StreamHandle>>);
+
+/// Start continuous microphone capture with VAD-gated transcription.
+///
+/// Emits `stt://partial` as speech accumulates and `stt://final` each time a
+/// pause closes an utterance. Call `stop_streaming_transcription` to stop.
+#[tauri::command]
+pub fn start_streaming_transcription(
+    app: AppHandle,
+    streaming_state: tauri::State<'_, StreamingState>,
+    whisper_state: tauri::State<'_, WhisperState>,
+    model_manager_state: tauri::State<'_, crate::model_manager::ModelManagerState>,
+    input_device_state: tauri::State<'_, InputDeviceState>,
+    language: String,
+) -> Result<(), String> {
+    let mut guard = streaming_state
+        .0
+        .lock()
+        .map_err(|_| "Streaming state poisoned".to_string())?;
+
+    if guard.is_some() {
+        return Err("Streaming transcription is already running".to_string());
+    }
+
+    let stop_flag = Arc::new(AtomicBool::new(false));
+    let (frame_tx, frame_rx): (Sender<Vec<f32>>, Receiver<Vec<f32>>) = bounded(32);
+    let (job_tx, job_rx): (Sender<TranscribeJob>, Receiver<TranscribeJob>) = unbounded();
+
+    let capture_stop = stop_flag.clone();
+    let selected_device = get_selected_input_device(&input_device_state);
+    let capture_thread =
+        std::thread::spawn(move || capture_loop(capture_stop, frame_tx, selected_device));
+
+    let worker_stop = stop_flag.clone();
+    let worker_thread =
+        std::thread::spawn(move || vad_worker_loop(worker_stop, frame_rx, job_tx));
+
+    let decode_stop = stop_flag.clone();
+    let decode_whisper_state = whisper_state.inner().clone();
+    let model_path = crate::model_manager::active_model_path(&model_manager_state);
+    let decode_thread = std::thread::spawn(move || {
+        decode_loop(decode_stop, job_rx, app, decode_whisper_state, model_path, language)
+    });
+
+    *guard = Some(StreamHandle {
+        stop_flag,
+        capture_thread: Some(capture_thread),
+        worker_thread: Some(worker_thread),
+        decode_thread: Some(decode_thread),
+    });
+
+    Ok(())
+}
+
+/// Stop the running streaming session started by `start_streaming_transcription`.
+#[tauri::command]
+pub fn stop_streaming_transcription(
+    streaming_state: tauri::State<'_, StreamingState>,
+) -> Result<(), String> {
+    let mut handle = streaming_state
+        .0
+        .lock()
+        .map_err(|_| "Streaming state poisoned".to_string())?
+        .take()
+        .ok_or_else(|| "Streaming transcription is not running".to_string())?;
+
+    handle.stop_flag.store(true, Ordering::SeqCst);
+
+    if let Some(thread) = handle.capture_thread.take() {
+        let _ = thread.join();
+    }
+    if let Some(thread) = handle.worker_thread.take() {
+        let _ = thread.join();
+    }
+    if let Some(thread) = handle.decode_thread.take() {
+        let _ = thread.join();
+    }
+
+    Ok(())
+}
+
+/// Capture microphone audio on the selected (or default) input device,
+/// resample to 16kHz mono, and push fixed-size frames into `frame_tx` until
+/// `stop_flag` is set.
+fn capture_loop(stop_flag: Arc<AtomicBool>, frame_tx: Sender<Vec<f32>>, selected_device: Option<String>) {
+    let host = cpal::default_host();
+    let device = match select_input_device(&host, selected_device.as_deref()) {
+        Some(device) => device,
+        None => {
+            log::warn!("No input device available for streaming transcription");
+            return;
+        }
+    };
+
+    let config = match device.default_input_config() {
+        Ok(config) => config,
+        Err(e) => {
+            log::warn!("Failed to read default input config: {}", e);
+            return;
+        }
+    };
+
+    let input_rate = config.sample_rate().0;
+    let channels = config.channels() as usize;
+    let sample_format = config.sample_format();
+    let stream_config: cpal::StreamConfig = config.into();
+    let mut pending = Vec::with_capacity(FRAME_SIZE * 2);
+
+    let stream_result = match sample_format {
+        cpal::SampleFormat::F32 => device.build_input_stream(
+            &stream_config,
+            move |data: &[f32], _| {
+                push_samples(&mut pending, data, channels, input_rate, &frame_tx);
+            },
+            |err| log::warn!("Input stream error: {}", err),
+            None,
+        ),
+        cpal::SampleFormat::I16 => device.build_input_stream(
+            &stream_config,
+            move |data: &[i16], _| {
+                let converted: Vec<f32> = data.iter().map(|&s| s as f32 / (i16::MAX as f32 + 1.0)).collect();
+                push_samples(&mut pending, &converted, channels, input_rate, &frame_tx);
+            },
+            |err| log::warn!("Input stream error: {}", err),
+            None,
+        ),
+        cpal::SampleFormat::U16 => device.build_input_stream(
+            &stream_config,
+            move |data: &[u16], _| {
+                let converted: Vec<f32> = data.iter().map(|&s| (s as f32 - 32_768.0) / 32_768.0).collect();
+                push_samples(&mut pending, &converted, channels, input_rate, &frame_tx);
+            },
+            |err| log::warn!("Input stream error: {}", err),
+            None,
+        ),
+        other => {
+            log::warn!("Unsupported input sample format: {:?}", other);
+            return;
+        }
+    };
+
+    let stream = match stream_result {
+        Ok(stream) => stream,
+        Err(e) => {
+            log::warn!("Failed to build input stream: {}", e);
+            return;
+        }
+    };
+
+    if let Err(e) = stream.play() {
+        log::warn!("Failed to start input stream: {}", e);
+        return;
+    }
+
+    while !stop_flag.load(Ordering::SeqCst) {
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+}
+
+/// Downmix and resample an f32 (already-converted if the native format wasn't
+/// f32) callback buffer, and push whole `FRAME_SIZE` frames into `frame_tx`.
+fn push_samples(
+    pending: &mut Vec<f32>,
+    data: &[f32],
+    channels: usize,
+    input_rate: u32,
+    frame_tx: &Sender<Vec<f32>>,
+) {
+    let mono = downmix_to_mono(data, channels);
+    let resampled = if input_rate == TARGET_SAMPLE_RATE {
+        mono
+    } else {
+        resample_linear(&mono, input_rate, TARGET_SAMPLE_RATE)
+    };
+    pending.extend_from_slice(&resampled);
+
+    while pending.len() >= FRAME_SIZE {
+        let frame: Vec<f32> = pending.drain(..FRAME_SIZE).collect();
+        if frame_tx.try_send(frame).is_err() {
+            log::warn!("Streaming transcription frame channel full, dropping frame");
+        }
+    }
+}
+
+/// Resolve the cpal device to capture from: the user's selection from
+/// `set_input_device`, matched by *name* against cpal's own device list (the
+/// index ffmpeg/pactl/arecord report has no relationship to cpal's
+/// enumeration order), falling back to the system default.
+fn select_input_device(host: &cpal::Host, selected_name: Option<&str>) -> Option<cpal::Device> {
+    if let Some(name) = selected_name {
+        match host.input_devices() {
+            Ok(devices) => {
+                if let Some(device) = devices.into_iter().find(|d| d.name().as_deref() == Ok(name)) {
+                    return Some(device);
+                }
+                log::warn!("Selected input device '{}' not found, falling back to default", name);
+            }
+            Err(e) => log::warn!("Failed to enumerate input devices: {}", e),
+        }
+    }
+
+    host.default_input_device()
+}
+
+/// Consume frames from `frame_rx`, gate utterance boundaries with a simple RMS
+/// voice-activity detector, and hand completed (or periodically throttled
+/// in-progress) utterances off to `job_tx` for transcription. Never calls
+/// into Whisper itself, so it keeps draining `frame_rx` even while a decode
+/// is in flight on the decode thread.
+fn vad_worker_loop(stop_flag: Arc<AtomicBool>, frame_rx: Receiver<Vec<f32>>, job_tx: Sender<TranscribeJob>) {
+    let frame_duration_ms = (FRAME_SIZE as u64 * 1000) / TARGET_SAMPLE_RATE as u64;
+    let silence_frames_to_close = (SILENCE_DURATION_MS / frame_duration_ms.max(1)).max(1);
+
+    let mut utterance: Vec<f32> = Vec::new();
+    let mut silence_run = 0u64;
+    let mut last_partial_at: Option<Instant> = None;
+
+    while !stop_flag.load(Ordering::SeqCst) {
+        let frame = match frame_rx.recv_timeout(Duration::from_millis(200)) {
+            Ok(frame) => frame,
+            Err(_) => continue,
+        };
+
+        let is_silence = rms(&frame) < SILENCE_RMS_THRESHOLD;
+
+        if is_silence {
+            silence_run += 1;
+        } else {
+            silence_run = 0;
+            utterance.extend_from_slice(&frame);
+
+            let due_for_partial = last_partial_at.map_or(true, |at| at.elapsed() >= PARTIAL_INTERVAL);
+            if due_for_partial {
+                last_partial_at = Some(Instant::now());
+                let _ = job_tx.send(TranscribeJob::Partial(utterance.clone()));
+            }
+        }
+
+        if !utterance.is_empty() && silence_run >= silence_frames_to_close {
+            let _ = job_tx.send(TranscribeJob::Final(std::mem::take(&mut utterance)));
+            silence_run = 0;
+            last_partial_at = None;
+        }
+    }
+}
+
+/// Run on a dedicated thread so a Whisper decode never blocks frame draining
+/// in `vad_worker_loop`. Consumes `TranscribeJob`s as fast as it can; if jobs
+/// arrive faster than they can be decoded, a run of queued `Partial`s is
+/// coalesced down to the newest one before each decode, stopping at the first
+/// `Final` so no utterance's final is dropped and a stale partial for an
+/// already-closed utterance is never emitted after that utterance's final.
+fn decode_loop(
+    stop_flag: Arc<AtomicBool>,
+    job_rx: Receiver<TranscribeJob>,
+    app: AppHandle,
+    whisper_state: WhisperState,
+    model_path: std::path::PathBuf,
+    language: String,
+) {
+    while !stop_flag.load(Ordering::SeqCst) {
+        let mut job = match job_rx.recv_timeout(Duration::from_millis(200)) {
+            Ok(job) => job,
+            Err(_) => continue,
+        };
+
+        while let TranscribeJob::Partial(_) = job {
+            match job_rx.try_recv() {
+                Ok(next) => job = next,
+                Err(_) => break,
+            }
+        }
+
+        match job {
+            TranscribeJob::Partial(utterance) => {
+                if let Ok(text) = transcribe_embedded(&whisper_state, &model_path, &utterance, &language) {
+                    let _ = app.emit("stt://partial", PartialTranscriptPayload { text });
+                }
+            }
+            TranscribeJob::Final(utterance) => {
+                match transcribe_embedded(&whisper_state, &model_path, &utterance, &language) {
+                    Ok(text) => {
+                        let _ = app.emit("stt://final", FinalTranscriptPayload { text });
+                    }
+                    Err(e) => log::warn!("Failed to transcribe utterance: {}", e),
+                }
+            }
+        }
+    }
+}
+
+/// Short-term root-mean-square energy of a frame, used as the VAD signal.
+fn rms(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let sum_sq: f32 = samples.iter().map(|s| s * s).sum();
+    (sum_sq / samples.len() as f32).sqrt()
+}
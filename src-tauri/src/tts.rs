@@ -0,0 +1,136 @@
+//! Cross-platform text-to-speech for spoken output
+//!
+//! Wraps the `tts` crate, which routes to native engines per platform
+//! (AVFoundation on macOS, Speech Dispatcher on Linux, WinRT/SAPI on Windows),
+//! so translations and example sentences can be read aloud without bundling a
+//! speech engine of our own.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use tts::Tts;
+
+/// Holds the lazily-initialized platform TTS engine, and whether a voice has
+/// been explicitly chosen via `set_voice`.
+#[derive(Default)]
+pub struct TtsState(Mutex<Option<Tts>>, AtomicBool);
+
+/// A voice available on this system, as returned by `list_voices`.
+#[derive(Clone, serde::Serialize)]
+pub struct VoiceInfo {
+    id: String,
+    name: String,
+    language: String,
+}
+
+/// Run `f` against the lazily-initialized TTS engine, creating it on first use.
+fn with_engine<T>(
+    state: &TtsState,
+    f: impl FnOnce(&mut Tts) -> Result<T, String>,
+) -> Result<T, String> {
+    let mut guard = state.0.lock().map_err(|_| "TTS state poisoned".to_string())?;
+
+    if guard.is_none() {
+        *guard = Some(Tts::default().map_err(|e| format!("Failed to initialize TTS engine: {}", e))?);
+    }
+
+    let engine = guard.as_mut().expect("just initialized above");
+    f(engine)
+}
+
+/// Loosely match a BCP-47 language tag against a requested one by comparing
+/// primary subtags (e.g. "en" matches both "en" and "en-US").
+fn language_matches(candidate: &str, requested: &str) -> bool {
+    let primary = |tag: &str| tag.split(['-', '_']).next().unwrap_or(tag).to_lowercase();
+    primary(candidate) == primary(requested)
+}
+
+/// Speak `text` aloud.
+///
+/// `language` is a BCP-47 tag (e.g. "en-US", "vi-VN") used to pick a default
+/// voice if one isn't already selected via `set_voice`. `rate` and `pitch` are
+/// passed straight to the underlying engine where supported.
+#[tauri::command]
+pub fn speak(
+    state: tauri::State<'_, TtsState>,
+    text: String,
+    language: String,
+    rate: f32,
+    pitch: f32,
+) -> Result<(), String> {
+    // Recording can leave macOS in the degraded voice-chat audio profile; make
+    // sure we're out of it before speaking so playback sounds right.
+    #[cfg(target_os = "macos")]
+    let _ = crate::audio_session::deactivate_voice_session();
+
+    let voice_selected = state.1.load(Ordering::SeqCst);
+
+    with_engine(&state, |engine| {
+        if !voice_selected {
+            if let Ok(voices) = engine.voices() {
+                if let Some(voice) = voices
+                    .into_iter()
+                    .find(|v| language_matches(&v.language().to_string(), &language))
+                {
+                    let _ = engine.set_voice(&voice);
+                }
+            }
+        }
+
+        let _ = engine.set_rate(rate);
+        let _ = engine.set_pitch(pitch);
+
+        engine
+            .speak(&text, true)
+            .map_err(|e| format!("Failed to speak: {}", e))?;
+
+        Ok(())
+    })
+}
+
+/// Stop any speech currently in progress.
+#[tauri::command]
+pub fn stop_speaking(state: tauri::State<'_, TtsState>) -> Result<(), String> {
+    with_engine(&state, |engine| {
+        engine.stop().map_err(|e| format!("Failed to stop speech: {}", e))
+    })
+}
+
+/// List voices available on this system, optionally filtered to those whose
+/// language matches `language` (empty string returns all voices).
+#[tauri::command]
+pub fn list_voices(
+    state: tauri::State<'_, TtsState>,
+    language: String,
+) -> Result<Vec<VoiceInfo>, String> {
+    with_engine(&state, |engine| {
+        let voices = engine.voices().map_err(|e| format!("Failed to list voices: {}", e))?;
+
+        Ok(voices
+            .into_iter()
+            .filter(|v| language.is_empty() || language_matches(&v.language().to_string(), &language))
+            .map(|v| VoiceInfo {
+                id: v.id(),
+                name: v.name(),
+                language: v.language().to_string(),
+            })
+            .collect())
+    })
+}
+
+/// Select a specific voice by id for subsequent `speak` calls.
+#[tauri::command]
+pub fn set_voice(state: tauri::State<'_, TtsState>, id: String) -> Result<(), String> {
+    with_engine(&state, |engine| {
+        let voices = engine.voices().map_err(|e| format!("Failed to list voices: {}", e))?;
+        let voice = voices
+            .into_iter()
+            .find(|v| v.id() == id)
+            .ok_or_else(|| format!("No voice found with id {}", id))?;
+
+        engine.set_voice(&voice).map_err(|e| format!("Failed to set voice: {}", e))
+    })?;
+
+    state.1.store(true, Ordering::SeqCst);
+    Ok(())
+}
@@ -1,11 +1,25 @@
 //! Whisper-based Speech-to-Text for Linux
 //!
 //! WebKitGTK doesn't support Web Speech API, so we use Whisper as a fallback.
-//! This module handles audio transcription via whisper.cpp CLI.
+//! Audio is transcribed in-process via whisper.cpp bindings (`whisper-rs`): the
+//! active ggml model (see `model_manager`) is loaded into a `WhisperContext`
+//! held in Tauri managed state, and decoded PCM samples are fed straight to it.
+//! If the embedded backend or the model is unavailable, we fall back to
+//! shelling out to a Whisper CLI.
 
 use std::io::Write;
 use std::process::Command;
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+/// Holds the loaded Whisper model, keyed by the path it was loaded from, so it
+/// only has to be (re)initialized when the active model changes. Cloning
+/// shares the same underlying context, which the streaming transcription
+/// worker relies on to move it onto a background thread.
+#[derive(Clone, Default)]
+pub struct WhisperState(Arc<Mutex<Option<(PathBuf, WhisperContext)>>>);
 
 /// Check if Whisper CLI is available on the system
 #[tauri::command]
@@ -35,10 +49,128 @@ pub fn check_whisper_available() -> Result<bool, String> {
     Ok(false)
 }
 
-/// Get the path to Whisper model (downloads if needed)
-fn get_whisper_model_path() -> PathBuf {
-    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
-    PathBuf::from(home).join(".cache/whisper/ggml-base.bin")
+/// Decode a WAV byte buffer into mono f32 samples at 16kHz, resampling if needed.
+pub(crate) fn decode_wav_to_f32_mono_16k(wav_bytes: &[u8]) -> Result<Vec<f32>, String> {
+    let mut reader = hound::WavReader::new(std::io::Cursor::new(wav_bytes))
+        .map_err(|e| format!("Failed to parse WAV: {}", e))?;
+    let spec = reader.spec();
+
+    let samples: Vec<f32> = match spec.sample_format {
+        hound::SampleFormat::Float => reader
+            .samples::<f32>()
+            .filter_map(Result::ok)
+            .collect(),
+        hound::SampleFormat::Int => {
+            let max = (1i64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .filter_map(Result::ok)
+                .map(|s| s as f32 / max)
+                .collect()
+        }
+    };
+
+    let mono = downmix_to_mono(&samples, spec.channels as usize);
+
+    Ok(if spec.sample_rate == 16_000 {
+        mono
+    } else {
+        resample_linear(&mono, spec.sample_rate, 16_000)
+    })
+}
+
+/// Average interleaved channels down to a single mono channel.
+pub(crate) fn downmix_to_mono(samples: &[f32], channels: usize) -> Vec<f32> {
+    if channels <= 1 {
+        return samples.to_vec();
+    }
+    samples
+        .chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect()
+}
+
+/// Simple linear resampler; good enough for voice audio going into Whisper.
+pub(crate) fn resample_linear(samples: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
+    if samples.is_empty() || from_rate == to_rate {
+        return samples.to_vec();
+    }
+
+    let ratio = from_rate as f64 / to_rate as f64;
+    let out_len = (samples.len() as f64 / ratio).round() as usize;
+
+    (0..out_len)
+        .map(|i| {
+            let src_pos = i as f64 * ratio;
+            let idx = src_pos.floor() as usize;
+            let frac = (src_pos - idx as f64) as f32;
+            let a = samples.get(idx).copied().unwrap_or(0.0);
+            let b = samples.get(idx + 1).copied().unwrap_or(a);
+            a + (b - a) * frac
+        })
+        .collect()
+}
+
+/// Transcribe PCM samples in-process using whisper-rs, (re)loading `model_path`
+/// into `state` if it isn't already the loaded model so later calls with the
+/// same active model reuse the same context.
+pub(crate) fn transcribe_embedded(
+    state: &WhisperState,
+    model_path: &std::path::Path,
+    samples: &[f32],
+    language: &str,
+) -> Result<String, String> {
+    let mut guard = state.0.lock().map_err(|_| "Whisper state poisoned".to_string())?;
+
+    let needs_load = match guard.as_ref() {
+        Some((loaded_path, _)) => loaded_path != model_path,
+        None => true,
+    };
+
+    if needs_load {
+        if !model_path.exists() {
+            return Err("Whisper model not downloaded".to_string());
+        }
+
+        let ctx = WhisperContext::new_with_params(
+            model_path.to_str().ok_or("Invalid model path")?,
+            WhisperContextParameters::default(),
+        )
+        .map_err(|e| format!("Failed to load Whisper model: {}", e))?;
+
+        *guard = Some((model_path.to_path_buf(), ctx));
+    }
+
+    let (_, ctx) = guard.as_ref().expect("just initialized above");
+
+    let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+    params.set_language(Some(language));
+    params.set_print_progress(false);
+    params.set_print_special(false);
+    params.set_print_realtime(false);
+
+    let mut fstate = ctx
+        .create_state()
+        .map_err(|e| format!("Failed to create Whisper inference state: {}", e))?;
+    fstate
+        .full(params, samples)
+        .map_err(|e| format!("Whisper inference failed: {}", e))?;
+
+    let num_segments = fstate
+        .full_n_segments()
+        .map_err(|e| format!("Failed to read Whisper segments: {}", e))?;
+
+    let mut text = String::new();
+    for i in 0..num_segments {
+        if let Ok(segment) = fstate.full_get_segment_text(i) {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            text.push_str(segment.trim());
+        }
+    }
+
+    Ok(text.trim().to_string())
 }
 
 /// Transcribe audio data using Whisper
@@ -46,14 +178,32 @@ fn get_whisper_model_path() -> PathBuf {
 /// # Arguments
 /// * `audio_data` - Base64 encoded WAV audio data
 /// * `language` - Language code (e.g., "en", "vi", "ja")
+///
+/// Prefers the embedded whisper-rs backend (no process spawn, model stays
+/// loaded across calls) and falls back to the Whisper CLI if the model or
+/// library isn't available.
 #[tauri::command]
-pub async fn transcribe_audio(audio_data: String, language: String) -> Result<String, String> {
+pub async fn transcribe_audio(
+    state: tauri::State<'_, WhisperState>,
+    model_manager_state: tauri::State<'_, crate::model_manager::ModelManagerState>,
+    audio_data: String,
+    language: String,
+) -> Result<String, String> {
     // Decode base64 audio data
     let audio_bytes = base64::Engine::decode(
         &base64::engine::general_purpose::STANDARD,
         &audio_data
     ).map_err(|e| format!("Failed to decode audio: {}", e))?;
 
+    let model_path = crate::model_manager::active_model_path(&model_manager_state);
+
+    match decode_wav_to_f32_mono_16k(&audio_bytes)
+        .and_then(|samples| transcribe_embedded(&state, &model_path, &samples, &language))
+    {
+        Ok(text) => return Ok(text),
+        Err(e) => log::warn!("Embedded Whisper backend unavailable, falling back to CLI: {}", e),
+    }
+
     // Create temp file for audio
     let temp_dir = std::env::temp_dir();
     let audio_path = temp_dir.join(format!("whisper_input_{}.wav", std::process::id()));
@@ -67,7 +217,7 @@ pub async fn transcribe_audio(audio_data: String, language: String) -> Result<St
     drop(file);
 
     // Try different Whisper CLI options
-    let result = try_whisper_cli(&audio_path, &output_path, &language).await;
+    let result = try_whisper_cli(&audio_path, &output_path, &language, &model_path).await;
 
     // Cleanup temp files
     let _ = std::fs::remove_file(&audio_path);
@@ -80,10 +230,9 @@ pub async fn transcribe_audio(audio_data: String, language: String) -> Result<St
 async fn try_whisper_cli(
     audio_path: &PathBuf,
     output_path: &PathBuf,
-    language: &str
+    language: &str,
+    model_path: &PathBuf,
 ) -> Result<String, String> {
-    let model_path = get_whisper_model_path();
-
     // Try whisper.cpp first (most common on Linux)
     let whisper_commands = [
         ("whisper", vec![
@@ -138,44 +287,20 @@ async fn try_whisper_cli(
     Err("Whisper not available. Install whisper.cpp: https://github.com/ggerganov/whisper.cpp".to_string())
 }
 
-/// Download Whisper model if not present
+/// Download the default ("base") Whisper model if not already present.
+///
+/// Kept for backwards compatibility with callers that predate per-model
+/// management; delegates to the model manager, which handles progress
+/// events and checksum verification.
 #[tauri::command]
-pub async fn ensure_whisper_model() -> Result<String, String> {
-    let model_path = get_whisper_model_path();
-
-    if model_path.exists() {
-        return Ok("Model already downloaded".to_string());
-    }
-
-    // Create cache directory
-    if let Some(parent) = model_path.parent() {
-        std::fs::create_dir_all(parent)
-            .map_err(|e| format!("Failed to create cache dir: {}", e))?;
-    }
-
-    // Download model using curl or wget
-    let model_url = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base.bin";
+pub async fn ensure_whisper_model(app: tauri::AppHandle) -> Result<String, String> {
+    let already_installed = crate::model_manager::model_path("base").exists();
 
-    let download_result = Command::new("curl")
-        .args(["-L", "-o", model_path.to_str().unwrap_or(""), model_url])
-        .output();
+    crate::model_manager::download_model(app, "base".to_string()).await?;
 
-    match download_result {
-        Ok(output) if output.status.success() => {
-            Ok("Model downloaded successfully".to_string())
-        }
-        _ => {
-            // Try wget as fallback
-            let wget_result = Command::new("wget")
-                .args(["-O", model_path.to_str().unwrap_or(""), model_url])
-                .output();
-
-            match wget_result {
-                Ok(output) if output.status.success() => {
-                    Ok("Model downloaded successfully".to_string())
-                }
-                _ => Err("Failed to download model. Install curl or wget.".to_string())
-            }
-        }
-    }
+    Ok(if already_installed {
+        "Model already downloaded".to_string()
+    } else {
+        "Model downloaded successfully".to_string()
+    })
 }
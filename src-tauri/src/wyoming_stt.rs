@@ -0,0 +1,187 @@
+//! Wyoming-protocol networked STT backend
+//!
+//! Wyoming is the newline-delimited-JSON-header plus binary-payload protocol used
+//! by Home Assistant voice satellites. This module speaks just enough of it to
+//! offload transcription to a remote Wyoming ASR service, so a thin client can use
+//! someone else's GPU (or an existing Wyoming STT server) instead of running
+//! Whisper locally.
+
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+use crate::whisper_stt::decode_wav_to_f32_mono_16k;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const SAMPLE_RATE: u32 = 16_000;
+const SAMPLE_WIDTH: u32 = 2;
+const CHANNELS: u32 = 1;
+/// Raw PCM bytes per `audio-chunk` event; matches the chunk size Wyoming's own
+/// reference clients use.
+const CHUNK_BYTES: usize = 4096;
+
+/// One Wyoming event: a JSON header plus an optional binary payload whose
+/// length is given by the header's `payload_length` field.
+struct WyomingEvent {
+    header: Value,
+    payload: Option<Vec<u8>>,
+}
+
+/// Write a Wyoming event: a JSON header line, followed by the raw payload
+/// bytes (if any), matching the protocol's header/payload framing.
+async fn write_event(
+    stream: &mut TcpStream,
+    event_type: &str,
+    data: Value,
+    payload: Option<&[u8]>,
+) -> Result<(), String> {
+    let mut header = json!({ "type": event_type, "data": data });
+    if let Some(payload) = payload {
+        header["payload_length"] = json!(payload.len());
+    }
+
+    let mut line = serde_json::to_vec(&header).map_err(|e| format!("Failed to encode Wyoming header: {}", e))?;
+    line.push(b'\n');
+
+    stream
+        .write_all(&line)
+        .await
+        .map_err(|e| format!("Failed to write Wyoming header: {}", e))?;
+
+    if let Some(payload) = payload {
+        stream
+            .write_all(payload)
+            .await
+            .map_err(|e| format!("Failed to write Wyoming payload: {}", e))?;
+    }
+
+    Ok(())
+}
+
+/// Read a single newline-terminated line from the stream.
+async fn read_line(stream: &mut TcpStream) -> Result<String, String> {
+    let mut line = Vec::new();
+    let mut byte = [0u8; 1];
+
+    loop {
+        let n = stream
+            .read(&mut byte)
+            .await
+            .map_err(|e| format!("Failed to read from Wyoming server: {}", e))?;
+        if n == 0 {
+            return Err("Wyoming server closed the connection".to_string());
+        }
+        if byte[0] == b'\n' {
+            break;
+        }
+        line.push(byte[0]);
+    }
+
+    String::from_utf8(line).map_err(|e| format!("Invalid UTF-8 from Wyoming server: {}", e))
+}
+
+/// Read one Wyoming event (header plus any binary payload it declares).
+async fn read_event(stream: &mut TcpStream) -> Result<WyomingEvent, String> {
+    let line = read_line(stream).await?;
+    let header: Value = serde_json::from_str(&line).map_err(|e| format!("Invalid Wyoming header: {}", e))?;
+
+    let payload_len = header
+        .get("payload_length")
+        .and_then(Value::as_u64)
+        .unwrap_or(0) as usize;
+
+    let payload = if payload_len > 0 {
+        let mut buf = vec![0u8; payload_len];
+        stream
+            .read_exact(&mut buf)
+            .await
+            .map_err(|e| format!("Failed to read Wyoming payload: {}", e))?;
+        Some(buf)
+    } else {
+        None
+    };
+
+    Ok(WyomingEvent { header, payload })
+}
+
+/// Convert mono f32 samples in [-1.0, 1.0] into little-endian 16-bit PCM bytes.
+fn f32_samples_to_pcm16_bytes(samples: &[f32]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(samples.len() * 2);
+    for &sample in samples {
+        let clamped = sample.clamp(-1.0, 1.0);
+        let pcm = (clamped * i16::MAX as f32) as i16;
+        bytes.extend_from_slice(&pcm.to_le_bytes());
+    }
+    bytes
+}
+
+/// Transcribe audio data via a remote Wyoming ASR server, parallel to
+/// `transcribe_audio` but offloading inference to `host:port` instead of
+/// running Whisper locally.
+///
+/// # Arguments
+/// * `audio_data` - Base64 encoded WAV audio data
+/// * `language` - Language code (e.g., "en", "vi", "ja")
+#[tauri::command]
+pub async fn transcribe_audio_wyoming(
+    host: String,
+    port: u16,
+    audio_data: String,
+    language: String,
+) -> Result<String, String> {
+    let audio_bytes = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, &audio_data)
+        .map_err(|e| format!("Failed to decode audio: {}", e))?;
+    let samples = decode_wav_to_f32_mono_16k(&audio_bytes)?;
+    let pcm = f32_samples_to_pcm16_bytes(&samples);
+
+    let mut stream = timeout(CONNECT_TIMEOUT, TcpStream::connect((host.as_str(), port)))
+        .await
+        .map_err(|_| "Timed out connecting to Wyoming server".to_string())?
+        .map_err(|e| format!("Failed to connect to Wyoming server: {}", e))?;
+
+    write_event(&mut stream, "transcribe", json!({ "language": language }), None).await?;
+    write_event(
+        &mut stream,
+        "audio-start",
+        json!({ "rate": SAMPLE_RATE, "width": SAMPLE_WIDTH, "channels": CHANNELS }),
+        None,
+    )
+    .await?;
+
+    for chunk in pcm.chunks(CHUNK_BYTES) {
+        write_event(
+            &mut stream,
+            "audio-chunk",
+            json!({ "rate": SAMPLE_RATE, "width": SAMPLE_WIDTH, "channels": CHANNELS }),
+            Some(chunk),
+        )
+        .await?;
+    }
+
+    write_event(&mut stream, "audio-stop", json!({}), None).await?;
+
+    loop {
+        let event = read_event(&mut stream).await?;
+        if event.header.get("type").and_then(Value::as_str) == Some("transcript") {
+            return event
+                .header
+                .get("data")
+                .and_then(|data| data.get("text"))
+                .and_then(Value::as_str)
+                .map(|text| text.to_string())
+                .ok_or_else(|| "Wyoming transcript event missing text".to_string());
+        }
+    }
+}
+
+/// Probe whether a Wyoming server is reachable at `host:port`, mirroring
+/// `check_whisper_available` for the networked backend.
+#[tauri::command]
+pub async fn check_wyoming_available(host: String, port: u16) -> Result<bool, String> {
+    Ok(timeout(CONNECT_TIMEOUT, TcpStream::connect((host.as_str(), port)))
+        .await
+        .is_ok_and(|result| result.is_ok()))
+}